@@ -1,11 +1,14 @@
 use std::{
+    any::Any,
     ffi::{CStr, CString},
     io::{Read, Seek, SeekFrom, Write},
     ops::DerefMut,
     slice,
 };
 
-use libc::{c_int, c_void};
+use libc::{
+    c_int, c_void, mode_t, S_IFBLK, S_IFCHR, S_IFDIR, S_IFIFO, S_IFLNK, S_IFMT, S_IFREG, S_IFSOCK,
+};
 
 use crate::{error::archive_result, ffi, ffi::UTF8LocaleGuard, Error, Result, READER_BUFFER_SIZE};
 
@@ -14,6 +17,64 @@ struct HeapReadSeekerPipe<R: Read + Seek> {
     buffer: [u8; READER_BUFFER_SIZE],
 }
 
+struct HeapReadPipe<R: Read> {
+    reader: R,
+    buffer: [u8; READER_BUFFER_SIZE],
+}
+
+/// The kind of file an archive entry represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    RegularFile,
+    Directory,
+    Symlink,
+    BlockDevice,
+    CharacterDevice,
+    Fifo,
+    Socket,
+    /// A file type libarchive reported that doesn't match any of the above.
+    Other,
+}
+
+impl FileType {
+    fn from_mode(mode: mode_t) -> FileType {
+        match mode & S_IFMT {
+            S_IFREG => FileType::RegularFile,
+            S_IFDIR => FileType::Directory,
+            S_IFLNK => FileType::Symlink,
+            S_IFBLK => FileType::BlockDevice,
+            S_IFCHR => FileType::CharacterDevice,
+            S_IFIFO => FileType::Fifo,
+            S_IFSOCK => FileType::Socket,
+            _ => FileType::Other,
+        }
+    }
+}
+
+/// Metadata describing an entry in the archive, as reported by libarchive
+/// once the entry's header has been read.
+#[derive(Debug, Clone)]
+pub struct ArchiveEntryMetadata {
+    /// The entry's pathname within the archive.
+    pub path: String,
+    /// The uncompressed size of the entry's data, in bytes.
+    pub size: i64,
+    /// The entry's last modification time, as a Unix timestamp.
+    pub mtime: i64,
+    /// The entry's Unix permission bits.
+    pub perm: u32,
+    /// The user id of the entry's owner.
+    pub uid: u64,
+    /// The group id of the entry's owner.
+    pub gid: u64,
+    /// The kind of file this entry represents.
+    pub file_type: FileType,
+    /// The target path, if this entry is a symlink.
+    pub symlink_target: Option<String>,
+    /// The target path, if this entry is a hardlink.
+    pub hardlink_target: Option<String>,
+}
+
 /// The contents of an archive, yielded in order from the beginning to the end
 /// of the archive.
 ///
@@ -24,7 +85,7 @@ struct HeapReadSeekerPipe<R: Read + Seek> {
 /// completion.
 pub enum ArchiveContents {
     /// Marks the start of an entry, either a file or a directory.
-    StartOfEntry(String),
+    StartOfEntry(ArchiveEntryMetadata),
     /// A chunk of uncompressed data from the entry. Entries may have zero or
     /// more chunks.
     DataChunk(Vec<u8>),
@@ -34,20 +95,66 @@ pub enum ArchiveContents {
     Err(Error),
 }
 
+/// Options controlling how an [`ArchiveIterator`] reads through an archive.
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveIteratorOptions {
+    /// When `false`, entry bodies are not streamed out as
+    /// [`ArchiveContents::DataChunk`]s; instead, each entry's data is fast
+    /// forwarded past with `archive_read_data_skip` on the call to `next()`
+    /// that follows its [`ArchiveContents::StartOfEntry`], which then yields
+    /// the entry's [`ArchiveContents::EndOfEntry`]. This is considerably cheaper than reading and discarding every
+    /// chunk when only the entries' metadata is needed.
+    pub read_data: bool,
+}
+
+impl Default for ArchiveIteratorOptions {
+    fn default() -> Self {
+        ArchiveIteratorOptions { read_data: true }
+    }
+}
+
+/// What to do with an entry, decided from its [`ArchiveEntryMetadata`] right
+/// after its header is read. Returned by the predicate passed to
+/// [`ArchiveIterator::from_read_filtered`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryAction {
+    /// Stream the entry's data as usual.
+    ReadData,
+    /// Fast-forward past the entry's data without reading it.
+    Skip,
+    /// Stop iterating immediately, without reading this entry at all.
+    Stop,
+}
+
+type EntryFilter = dyn FnMut(&ArchiveEntryMetadata) -> EntryAction;
+
 /// An iterator over the contents of an archive.
-pub struct ArchiveIterator<R: Read + Seek> {
+///
+/// **Breaking change:** this type used to be generic over the underlying
+/// reader (`ArchiveIterator<R: Read + Seek>`). Supporting
+/// [`ArchiveIterator::from_read_stream`], whose reader is only `Read` (not
+/// `Seek`), meant the two constructors could no longer share a single
+/// generic `R`, so the reader is now type-erased behind `Box<dyn Any>`
+/// internally and `ArchiveIterator` is no longer generic at all. Any code
+/// naming the type as `ArchiveIterator<R>` (struct fields, type aliases,
+/// etc.) needs to drop the type parameter; this warrants a major version
+/// bump.
+pub struct ArchiveIterator {
     archive_entry: *mut ffi::archive_entry,
     archive_reader: *mut ffi::archive,
 
     in_file: bool,
     closed: bool,
     error: bool,
+    options: ArchiveIteratorOptions,
+    filter: Option<Box<EntryFilter>>,
+    pending_action: EntryAction,
 
-    _pipe: Box<HeapReadSeekerPipe<R>>,
+    _pipe: Box<dyn Any>,
     _utf8_guard: UTF8LocaleGuard,
 }
 
-impl<R: Read + Seek> Iterator for ArchiveIterator<R> {
+impl Iterator for ArchiveIterator {
     type Item = ArchiveContents;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -56,11 +163,31 @@ impl<R: Read + Seek> Iterator for ArchiveIterator<R> {
         }
 
         let next = if self.in_file {
-            unsafe { self.next_data_chunk() }
+            if self.pending_action == EntryAction::Skip || !self.options.read_data {
+                unsafe { self.skip_entry_data() }
+            } else {
+                unsafe { self.next_data_chunk() }
+            }
         } else {
             unsafe { self.next_header() }
         };
 
+        if let ArchiveContents::StartOfEntry(meta) = &next {
+            self.pending_action = match self.filter.as_mut() {
+                Some(filter) => filter(meta),
+                None => EntryAction::ReadData,
+            };
+
+            if self.pending_action == EntryAction::Stop {
+                // Latch the stop the same way an `Err` does, so a caller
+                // that polls past the first `None` (e.g. a generic retry
+                // loop that doesn't assume the iterator is fused) doesn't
+                // resume iteration past the entry that asked to stop.
+                self.error = true;
+                return None;
+            }
+        }
+
         match &next {
             ArchiveContents::StartOfEntry(_) => {
                 self.in_file = true;
@@ -80,13 +207,13 @@ impl<R: Read + Seek> Iterator for ArchiveIterator<R> {
     }
 }
 
-impl<R: Read + Seek> Drop for ArchiveIterator<R> {
+impl Drop for ArchiveIterator {
     fn drop(&mut self) {
         drop(self.free());
     }
 }
 
-impl<R: Read + Seek> ArchiveIterator<R> {
+impl ArchiveIterator {
     /// Iterate over the contents of an archive, streaming the contents of each
     /// entry in small chunks.
     ///
@@ -103,7 +230,7 @@ impl<R: Read + Seek> ArchiveIterator<R> {
     ///
     /// for content in &mut iter {
     ///     match content {
-    ///         ArchiveContents::StartOfEntry(s) => name = s,
+    ///         ArchiveContents::StartOfEntry(meta) => name = meta.path,
     ///         ArchiveContents::DataChunk(v) => size += v.len(),
     ///         ArchiveContents::EndOfEntry => {
     ///             println!("Entry {} was {} bytes", name, size);
@@ -119,7 +246,25 @@ impl<R: Read + Seek> ArchiveIterator<R> {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn from_read(source: R) -> Result<ArchiveIterator<R>>
+    pub fn from_read<R>(source: R) -> Result<ArchiveIterator>
+    where
+        R: Read + Seek + 'static,
+    {
+        Self::from_read_with_options(source, ArchiveIteratorOptions::default())
+    }
+
+    /// Iterate over the contents of an archive, with control over whether
+    /// entry data is streamed out or skipped.
+    ///
+    /// Passing an [`ArchiveIteratorOptions`] with `read_data: false` turns
+    /// every entry's body into a single fast skip, so scanning an archive
+    /// for just its metadata no longer pays the cost of decompressing every
+    /// entry. See [`ArchiveIterator::skip_current_entry`] for skipping
+    /// entries selectively instead.
+    pub fn from_read_with_options<R>(
+        source: R,
+        options: ArchiveIteratorOptions,
+    ) -> Result<ArchiveIterator>
     where
         R: Read + Seek + 'static,
     {
@@ -129,20 +274,9 @@ impl<R: Read + Seek> ArchiveIterator<R> {
         let mut pipe = Box::new(HeapReadSeekerPipe { reader, buffer });
 
         unsafe {
-            let archive_entry: *mut ffi::archive_entry = std::ptr::null_mut();
-            let archive_reader = ffi::archive_read_new();
-
-            let res = (|| {
-                archive_result(
-                    ffi::archive_read_support_filter_all(archive_reader),
-                    archive_reader,
-                )?;
-
-                archive_result(
-                    ffi::archive_read_support_format_all(archive_reader),
-                    archive_reader,
-                )?;
+            let (archive_reader, res) = Self::new_archive_reader();
 
+            let res = res.and_then(|_| {
                 archive_result(
                     ffi::archive_read_set_seek_callback(
                         archive_reader,
@@ -151,10 +285,6 @@ impl<R: Read + Seek> ArchiveIterator<R> {
                     archive_reader,
                 )?;
 
-                if archive_reader.is_null() {
-                    return Err(Error::NullArchive);
-                }
-
                 archive_result(
                     ffi::archive_read_open(
                         archive_reader,
@@ -164,28 +294,103 @@ impl<R: Read + Seek> ArchiveIterator<R> {
                         None,
                     ),
                     archive_reader,
-                )?;
+                )
+            });
 
-                Ok(())
-            })();
+            Self::finish_setup(archive_reader, options, pipe, utf8_guard, res)
+        }
+    }
 
-            let iter = ArchiveIterator {
-                archive_entry,
-                archive_reader,
+    /// Iterate over the contents of an archive read from a non-seekable
+    /// stream, such as a pipe, socket, or stdin.
+    ///
+    /// Most archive formats can be read sequentially, so this only requires
+    /// `R: Read` instead of the `Read + Seek` that [`ArchiveIterator::from_read`]
+    /// needs; the trade-off is that formats relying on random access, such as
+    /// ZIP, may not be readable this way.
+    pub fn from_read_stream<R>(source: R) -> Result<ArchiveIterator>
+    where
+        R: Read + 'static,
+    {
+        Self::from_read_stream_with_options(source, ArchiveIteratorOptions::default())
+    }
 
-                in_file: false,
-                closed: false,
-                error: false,
+    /// Like [`ArchiveIterator::from_read_stream`], with control over whether
+    /// entry data is streamed out or skipped; see
+    /// [`ArchiveIterator::from_read_with_options`].
+    pub fn from_read_stream_with_options<R>(
+        source: R,
+        options: ArchiveIteratorOptions,
+    ) -> Result<ArchiveIterator>
+    where
+        R: Read + 'static,
+    {
+        let utf8_guard = ffi::UTF8LocaleGuard::new();
+        let reader = source;
+        let buffer = [0; READER_BUFFER_SIZE];
+        let mut pipe = Box::new(HeapReadPipe { reader, buffer });
 
-                _pipe: pipe,
-                _utf8_guard: utf8_guard,
-            };
+        unsafe {
+            let (archive_reader, res) = Self::new_archive_reader();
 
-            res?;
-            Ok(iter)
+            let res = res.and_then(|_| {
+                archive_result(
+                    ffi::archive_read_open(
+                        archive_reader,
+                        (pipe.deref_mut() as *mut HeapReadPipe<R>) as *mut c_void,
+                        None,
+                        Some(libarchive_heap_read_callback::<R>),
+                        None,
+                    ),
+                    archive_reader,
+                )
+            });
+
+            Self::finish_setup(archive_reader, options, pipe, utf8_guard, res)
         }
     }
 
+    /// Iterate over the contents of an archive, deciding what to do with each
+    /// entry's data right after its header is read.
+    ///
+    /// `filter` is called with each entry's [`ArchiveEntryMetadata`] as soon
+    /// as it is available, and its returned [`EntryAction`] decides whether
+    /// the entry's data is streamed out as usual, fast-skipped via
+    /// `archive_read_data_skip`, or whether iteration stops altogether. This
+    /// composes the skip-mode machinery from
+    /// [`ArchiveIterator::from_read_with_options`] with the entry metadata,
+    /// so callers can, say, extract only the entries under a given path
+    /// prefix from a huge archive without paying to decompress the rest.
+    pub fn from_read_filtered<R, F>(source: R, filter: F) -> Result<ArchiveIterator>
+    where
+        R: Read + Seek + 'static,
+        F: FnMut(&ArchiveEntryMetadata) -> EntryAction + 'static,
+    {
+        let mut iter = Self::from_read_with_options(source, ArchiveIteratorOptions::default())?;
+        iter.filter = Some(Box::new(filter));
+        Ok(iter)
+    }
+
+    /// Skip over the data of the entry that is currently being read, without
+    /// decompressing it.
+    ///
+    /// This lets callers mix the two modes of operation: read the data of
+    /// the entries they care about as usual, and skip the rest cheaply via
+    /// `archive_read_data_skip` instead of draining and discarding every
+    /// [`ArchiveContents::DataChunk`]. The skip is performed lazily, on the
+    /// next call to `next()`, through the same path as the `read_data:
+    /// false` and [`EntryAction::Skip`] modes, so the entry still gets its
+    /// matching [`ArchiveContents::EndOfEntry`] instead of having it
+    /// silently dropped. It is a no-op if called outside of an entry, e.g.
+    /// before the first `StartOfEntry` or after an `EndOfEntry`.
+    pub fn skip_current_entry(&mut self) -> Result<()> {
+        if self.in_file {
+            self.pending_action = EntryAction::Skip;
+        }
+
+        Ok(())
+    }
+
     /// Close the iterator, freeing up the associated resources.
     ///
     /// Resources will be freed on drop if this is not called, but any errors
@@ -213,19 +418,89 @@ impl<R: Read + Seek> ArchiveIterator<R> {
         Ok(())
     }
 
+    /// Creates a new `archive_reader` with filter and format auto-detection
+    /// enabled, shared by every `from_read*` constructor. Returns the reader
+    /// alongside the result of that setup rather than bailing out early, so
+    /// callers can still build an [`ArchiveIterator`] around it and let
+    /// `Drop` free it even when setup failed.
+    unsafe fn new_archive_reader() -> (*mut ffi::archive, Result<()>) {
+        let archive_reader = ffi::archive_read_new();
+
+        let res = (|| {
+            archive_result(
+                ffi::archive_read_support_filter_all(archive_reader),
+                archive_reader,
+            )?;
+
+            archive_result(
+                ffi::archive_read_support_format_all(archive_reader),
+                archive_reader,
+            )?;
+
+            if archive_reader.is_null() {
+                return Err(Error::NullArchive);
+            }
+
+            Ok(())
+        })();
+
+        (archive_reader, res)
+    }
+
+    /// Assembles the [`ArchiveIterator`] once `archive_reader` has been
+    /// opened (or failed to), shared by every `from_read*` constructor.
+    fn finish_setup(
+        archive_reader: *mut ffi::archive,
+        options: ArchiveIteratorOptions,
+        pipe: Box<dyn Any>,
+        utf8_guard: UTF8LocaleGuard,
+        res: Result<()>,
+    ) -> Result<ArchiveIterator> {
+        let iter = ArchiveIterator {
+            archive_entry: std::ptr::null_mut(),
+            archive_reader,
+
+            in_file: false,
+            closed: false,
+            error: false,
+            options,
+            filter: None,
+            pending_action: EntryAction::ReadData,
+
+            _pipe: pipe,
+            _utf8_guard: utf8_guard,
+        };
+
+        res?;
+        Ok(iter)
+    }
+
     unsafe fn next_header(&mut self) -> ArchiveContents {
         match ffi::archive_read_next_header(self.archive_reader, &mut self.archive_entry) {
             ffi::ARCHIVE_EOF => ArchiveContents::EndOfEntry,
-            ffi::ARCHIVE_OK => {
-                let file_name = CStr::from_ptr(ffi::archive_entry_pathname(self.archive_entry))
-                    .to_string_lossy()
-                    .into_owned();
-                ArchiveContents::StartOfEntry(file_name)
-            }
+            ffi::ARCHIVE_OK => ArchiveContents::StartOfEntry(self.entry_metadata()),
             _ => ArchiveContents::Err(Error::from(self.archive_reader)),
         }
     }
 
+    unsafe fn entry_metadata(&self) -> ArchiveEntryMetadata {
+        let path = CStr::from_ptr(ffi::archive_entry_pathname(self.archive_entry))
+            .to_string_lossy()
+            .into_owned();
+
+        ArchiveEntryMetadata {
+            path,
+            size: ffi::archive_entry_size(self.archive_entry),
+            mtime: ffi::archive_entry_mtime(self.archive_entry),
+            perm: ffi::archive_entry_perm(self.archive_entry) as u32,
+            uid: ffi::archive_entry_uid(self.archive_entry) as u64,
+            gid: ffi::archive_entry_gid(self.archive_entry) as u64,
+            file_type: FileType::from_mode(ffi::archive_entry_filetype(self.archive_entry)),
+            symlink_target: cstr_opt(ffi::archive_entry_symlink(self.archive_entry)),
+            hardlink_target: cstr_opt(ffi::archive_entry_hardlink(self.archive_entry)),
+        }
+    }
+
     unsafe fn next_data_chunk(&mut self) -> ArchiveContents {
         let mut buffer = std::ptr::null();
         let mut offset = 0;
@@ -247,6 +522,21 @@ impl<R: Read + Seek> ArchiveIterator<R> {
             _ => ArchiveContents::Err(Error::from(self.archive_reader)),
         }
     }
+
+    unsafe fn skip_entry_data(&mut self) -> ArchiveContents {
+        match ffi::archive_read_data_skip(self.archive_reader) {
+            ffi::ARCHIVE_OK | ffi::ARCHIVE_EOF => ArchiveContents::EndOfEntry,
+            _ => ArchiveContents::Err(Error::from(self.archive_reader)),
+        }
+    }
+}
+
+unsafe fn cstr_opt(ptr: *const libc::c_char) -> Option<String> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+    }
 }
 
 unsafe extern "C" fn libarchive_heap_seek_callback<R: Read + Seek>(
@@ -293,3 +583,293 @@ unsafe extern "C" fn libarchive_heap_seekableread_callback<R: Read + Seek>(
         }
     }
 }
+
+unsafe extern "C" fn libarchive_heap_read_callback<R: Read>(
+    archive: *mut ffi::archive,
+    client_data: *mut c_void,
+    buffer: *mut *const c_void,
+) -> ffi::la_ssize_t {
+    let pipe = (client_data as *mut HeapReadPipe<R>).as_mut().unwrap();
+
+    *buffer = pipe.buffer.as_ptr() as *const c_void;
+
+    match pipe.reader.read(&mut pipe.buffer) {
+        Ok(size) => size as ffi::la_ssize_t,
+        Err(e) => {
+            let description = CString::new(e.to_string()).unwrap();
+
+            ffi::archive_set_error(archive, e.raw_os_error().unwrap_or(0), description.as_ptr());
+
+            -1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// A single entry to be written into a [`build_tar`] archive.
+    struct TarEntry<'a> {
+        name: &'a str,
+        data: &'a [u8],
+        typeflag: u8,
+        linkname: Option<&'a str>,
+    }
+
+    impl<'a> TarEntry<'a> {
+        fn file(name: &'a str, data: &'a [u8]) -> Self {
+            TarEntry {
+                name,
+                data,
+                typeflag: b'0',
+                linkname: None,
+            }
+        }
+
+        fn symlink(name: &'a str, target: &'a str) -> Self {
+            TarEntry {
+                name,
+                data: &[],
+                typeflag: b'2',
+                linkname: Some(target),
+            }
+        }
+    }
+
+    /// Builds a minimal in-memory ustar archive containing the given
+    /// entries, for exercising the iterator without needing a fixture file
+    /// on disk.
+    fn build_tar(entries: &[TarEntry]) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        for entry in entries {
+            let mut header = [0u8; 512];
+            header[..entry.name.len()].copy_from_slice(entry.name.as_bytes());
+            header[100..108].copy_from_slice(b"0000644\0");
+            header[108..116].copy_from_slice(b"0000000\0");
+            header[116..124].copy_from_slice(b"0000000\0");
+            header[124..136].copy_from_slice(format!("{:011o}\0", entry.data.len()).as_bytes());
+            header[136..148].copy_from_slice(b"00000000000\0");
+            header[148..156].copy_from_slice(b"        ");
+            header[156] = entry.typeflag;
+            if let Some(linkname) = entry.linkname {
+                header[157..157 + linkname.len()].copy_from_slice(linkname.as_bytes());
+            }
+            header[257..263].copy_from_slice(b"ustar\0");
+            header[263..265].copy_from_slice(b"00");
+
+            let checksum: u32 = header.iter().map(|&b| b as u32).sum();
+            header[148..155].copy_from_slice(format!("{:06o}\0", checksum).as_bytes());
+
+            out.extend_from_slice(&header);
+            out.extend_from_slice(entry.data);
+            let padding = (512 - entry.data.len() % 512) % 512;
+            out.extend(std::iter::repeat(0u8).take(padding));
+        }
+
+        out.extend(std::iter::repeat(0u8).take(1024));
+        out
+    }
+
+    /// A mixed workflow that skips some entries and reads others must still
+    /// yield a `StartOfEntry`/`EndOfEntry` pair for every entry, including
+    /// the skipped ones, per the invariant documented on [`ArchiveContents`].
+    #[test]
+    fn skip_current_entry_still_emits_end_of_entry() {
+        let archive = build_tar(&[
+            TarEntry::file("skip-me.txt", b"discarded"),
+            TarEntry::file("read-me.txt", b"kept"),
+            TarEntry::file("skip-me-too.txt", b"also discarded"),
+        ]);
+
+        let mut iter = ArchiveIterator::from_read(Cursor::new(archive)).unwrap();
+        let mut events = Vec::new();
+        let mut read_me_data = Vec::new();
+
+        while let Some(content) = iter.next() {
+            match content {
+                ArchiveContents::StartOfEntry(meta) => {
+                    events.push(format!("start:{}", meta.path));
+                    if meta.path != "read-me.txt" {
+                        iter.skip_current_entry().unwrap();
+                    }
+                }
+                ArchiveContents::DataChunk(chunk) => read_me_data.extend(chunk),
+                ArchiveContents::EndOfEntry => events.push("end".to_string()),
+                ArchiveContents::Err(e) => panic!("unexpected error: {e}"),
+            }
+        }
+
+        iter.close().unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                "start:skip-me.txt".to_string(),
+                "end".to_string(),
+                "start:read-me.txt".to_string(),
+                "end".to_string(),
+                "start:skip-me-too.txt".to_string(),
+                "end".to_string(),
+            ]
+        );
+        assert_eq!(read_me_data, b"kept");
+    }
+
+    /// `EntryAction::Stop` must latch: once a filter returns `Stop`, the
+    /// iterator should keep returning `None` forever after, not resume
+    /// iteration on a later `next()` call (e.g. from a retry/poll loop that
+    /// doesn't assume the iterator is fused).
+    #[test]
+    fn filter_stop_is_latched() {
+        let archive = build_tar(&[
+            TarEntry::file("e1.txt", b"one"),
+            TarEntry::file("e2.txt", b"two"),
+            TarEntry::file("e3.txt", b"three"),
+        ]);
+
+        let mut iter =
+            ArchiveIterator::from_read_filtered(Cursor::new(archive), |meta| {
+                if meta.path == "e2.txt" {
+                    EntryAction::Stop
+                } else {
+                    EntryAction::ReadData
+                }
+            })
+            .unwrap();
+
+        let mut paths = Vec::new();
+        while let Some(content) = iter.next() {
+            if let ArchiveContents::StartOfEntry(meta) = content {
+                paths.push(meta.path);
+            }
+        }
+        assert_eq!(paths, vec!["e1.txt".to_string()]);
+
+        for _ in 0..3 {
+            assert!(iter.next().is_none());
+        }
+    }
+
+    /// Entries for which the filter returns `Skip` should have their data
+    /// fast-forwarded past, never surfacing a `DataChunk`, while still
+    /// getting their `StartOfEntry`/`EndOfEntry` pair like any other entry.
+    #[test]
+    fn filter_skip_fast_forwards_without_data_chunks() {
+        let archive = build_tar(&[
+            TarEntry::file("skip.txt", b"discarded"),
+            TarEntry::file("read.txt", b"kept"),
+        ]);
+
+        let mut iter = ArchiveIterator::from_read_filtered(Cursor::new(archive), |meta| {
+            if meta.path == "skip.txt" {
+                EntryAction::Skip
+            } else {
+                EntryAction::ReadData
+            }
+        })
+        .unwrap();
+
+        let mut events = Vec::new();
+        let mut read_data = Vec::new();
+
+        while let Some(content) = iter.next() {
+            match content {
+                ArchiveContents::StartOfEntry(meta) => events.push(format!("start:{}", meta.path)),
+                ArchiveContents::DataChunk(chunk) => read_data.extend(chunk),
+                ArchiveContents::EndOfEntry => events.push("end".to_string()),
+                ArchiveContents::Err(e) => panic!("unexpected error: {e}"),
+            }
+        }
+
+        iter.close().unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                "start:skip.txt".to_string(),
+                "end".to_string(),
+                "start:read.txt".to_string(),
+                "end".to_string(),
+            ]
+        );
+        assert_eq!(read_data, b"kept");
+    }
+
+    /// A reader that only implements `Read`, to make sure
+    /// `from_read_stream` really doesn't require `Seek`.
+    struct OnlyRead<R>(R);
+
+    impl<R: Read> Read for OnlyRead<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    #[test]
+    fn from_read_stream_reads_a_non_seekable_source() {
+        let archive = build_tar(&[TarEntry::file("stream.txt", b"streamed")]);
+        let mut iter = ArchiveIterator::from_read_stream(OnlyRead(Cursor::new(archive))).unwrap();
+
+        let mut path = String::new();
+        let mut data = Vec::new();
+        let mut saw_end = false;
+
+        while let Some(content) = iter.next() {
+            match content {
+                ArchiveContents::StartOfEntry(meta) => path = meta.path,
+                ArchiveContents::DataChunk(chunk) => data.extend(chunk),
+                ArchiveContents::EndOfEntry => saw_end = true,
+                ArchiveContents::Err(e) => panic!("unexpected error: {e}"),
+            }
+        }
+
+        iter.close().unwrap();
+
+        assert_eq!(path, "stream.txt");
+        assert_eq!(data, b"streamed");
+        assert!(saw_end);
+    }
+
+    /// `StartOfEntry` should carry the entry's full metadata, not just its
+    /// path, including the symlink target for symlink entries.
+    #[test]
+    fn metadata_is_populated_for_files_and_symlinks() {
+        let archive = build_tar(&[
+            TarEntry::file("regular.txt", b"hi"),
+            TarEntry::symlink("link.txt", "regular.txt"),
+        ]);
+
+        let mut iter = ArchiveIterator::from_read(Cursor::new(archive)).unwrap();
+
+        let regular = loop {
+            match iter.next().unwrap() {
+                ArchiveContents::StartOfEntry(meta) => break meta,
+                ArchiveContents::Err(e) => panic!("unexpected error: {e}"),
+                _ => continue,
+            }
+        };
+        assert_eq!(regular.path, "regular.txt");
+        assert_eq!(regular.size, 2);
+        assert_eq!(regular.perm, 0o644);
+        assert_eq!(regular.file_type, FileType::RegularFile);
+        assert_eq!(regular.symlink_target, None);
+        while !matches!(iter.next().unwrap(), ArchiveContents::EndOfEntry) {}
+
+        let link = loop {
+            match iter.next().unwrap() {
+                ArchiveContents::StartOfEntry(meta) => break meta,
+                ArchiveContents::Err(e) => panic!("unexpected error: {e}"),
+                _ => continue,
+            }
+        };
+        assert_eq!(link.path, "link.txt");
+        assert_eq!(link.file_type, FileType::Symlink);
+        assert_eq!(link.symlink_target, Some("regular.txt".to_string()));
+
+        iter.close().unwrap();
+    }
+}